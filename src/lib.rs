@@ -47,7 +47,9 @@
 //! memory size (128/256 bytes vs. 16/32), which does not increase with the stacks
 //! added, so when 8 sets (the maximum number) are used in one stack,
 //! the memory size is equivalent.
-#![no_std]
+// The optional `std` feature only enables runtime CPU-feature detection for
+// `AnyByteStack::classify`'s vectorized path; everything else stays `no_std`.
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 mod bit;
 /// Types that denote the position of a byte set within a byte stack.
@@ -110,6 +112,47 @@ impl AsciiSet {
         let mask = 1 << (byte as usize % BITS_PER_CHUNK);
         (chunk & mask) != 0
     }
+
+    /// Groups all bytes into equivalence classes according to whether they
+    /// belong to this set, so that two bytes in the same class are
+    /// indistinguishable to this set's `contains`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// let classes = AsciiSet::LOWERCASE.classes();
+    /// assert_eq!(classes.get(b'a'), classes.get(b'z'));
+    /// assert_ne!(classes.get(b'a'), classes.get(b'0'));
+    /// ```
+    pub fn classes(&self) -> ByteClasses {
+        byte_classes_from_profiles(|byte| self.contains(byte) as u8)
+    }
+
+    /// Returns the length of the leading prefix of `input` whose bytes all
+    /// belong to this set.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// assert_eq!(AsciiSet::ALPHANUMERIC.match_len(b"ab12 cd"), 4);
+    /// ```
+    pub fn match_len(&self, input: &[u8]) -> usize {
+        input.iter().take_while(|&&byte| self.contains(byte)).count()
+    }
+
+    /// Splits `input` right after the leading prefix of bytes that all
+    /// belong to this set, returning `(prefix, rest)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// let (ident, rest) = AsciiSet::ALPHANUMERIC.split_first_span(b"ab12 cd");
+    /// assert_eq!(ident, b"ab12");
+    /// assert_eq!(rest, b" cd");
+    /// ```
+    pub fn split_first_span<'a>(&self, input: &'a [u8]) -> (&'a [u8], &'a [u8]) {
+        input.split_at(self.match_len(input))
+    }
 }
 
 impl ByteSet {
@@ -127,6 +170,47 @@ impl ByteSet {
         let mask = 1 << (byte as usize % BITS_PER_CHUNK);
         (chunk & mask) != 0
     }
+
+    /// Groups all bytes into equivalence classes according to whether they
+    /// belong to this set, so that two bytes in the same class are
+    /// indistinguishable to this set's `contains`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::ByteSet;
+    /// let classes = ByteSet::LOWERCASE.classes();
+    /// assert_eq!(classes.get(b'a'), classes.get(b'z'));
+    /// assert_ne!(classes.get(b'a'), classes.get(b'0'));
+    /// ```
+    pub fn classes(&self) -> ByteClasses {
+        byte_classes_from_profiles(|byte| self.contains(byte) as u8)
+    }
+
+    /// Returns the length of the leading prefix of `input` whose bytes all
+    /// belong to this set.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::ByteSet;
+    /// assert_eq!(ByteSet::ALPHANUMERIC.match_len(b"ab12 cd"), 4);
+    /// ```
+    pub fn match_len(&self, input: &[u8]) -> usize {
+        input.iter().take_while(|&&byte| self.contains(byte)).count()
+    }
+
+    /// Splits `input` right after the leading prefix of bytes that all
+    /// belong to this set, returning `(prefix, rest)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::ByteSet;
+    /// let (ident, rest) = ByteSet::ALPHANUMERIC.split_first_span(b"ab12 cd");
+    /// assert_eq!(ident, b"ab12");
+    /// assert_eq!(rest, b" cd");
+    /// ```
+    pub fn split_first_span<'a>(&self, input: &'a [u8]) -> (&'a [u8], &'a [u8]) {
+        input.split_at(self.match_len(input))
+    }
 }
 
 impl<const N: usize> AnyByteSet<N> {
@@ -195,24 +279,30 @@ impl<const N: usize> AnyByteSet<N> {
 
     /// Adds every byte from the inclusive range to the set.
     pub const fn add_range(&self, range: RangeInclusive<u8>) -> Self {
+        if *range.start() > *range.end() {
+            return *self;
+        }
         let mut aset = *self;
         let mut c = *range.start();
-        while c <= *range.end() {
+        while c < *range.end() {
             aset = aset.add(c);
             c += 1;
         }
-        aset
+        aset.add(*range.end())
     }
 
     /// Removes every byte from the inclusive range from the set.
     pub const fn remove_range(&self, range: RangeInclusive<u8>) -> Self {
+        if *range.start() > *range.end() {
+            return *self;
+        }
         let mut aset = *self;
         let mut c = *range.start();
-        while c <= *range.end() {
+        while c < *range.end() {
             aset = aset.remove(c);
             c += 1;
         }
-        aset
+        aset.remove(*range.end())
     }
 
     /// Returns the union of this set and `other`.
@@ -267,7 +357,7 @@ impl<const N: usize> AnyByteSet<N> {
     }
 
     /// Returns the set of chars in `self` but not `other`.
-    /// 
+    ///
     /// #Panics
     /// Panics if the size of `other` is bigger than the size of `self`.
     ///
@@ -279,6 +369,448 @@ impl<const N: usize> AnyByteSet<N> {
     pub const fn difference<const M: usize>(&self, other: AnyByteSet<M>) -> Self {
         self.intersection(other.complement())
     }
+
+    /// Returns the set of bytes in exactly one of `self` and `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// let digit_or_lowercase_but_not_both = AsciiSet::DIGITS.symmetric_difference(AsciiSet::LOWERCASE);
+    /// assert!(digit_or_lowercase_but_not_both.contains(b'0'));
+    /// assert!(digit_or_lowercase_but_not_both.contains(b'a'));
+    /// ```
+    pub const fn symmetric_difference(&self, other: Self) -> Self {
+        let mut mask = [0; N];
+        let mut i = 0;
+        while i < N {
+            mask[i] = self.mask[i] ^ other.mask[i];
+            i += 1;
+        }
+        Self { mask }
+    }
+
+    /// Returns the number of bytes contained in this set.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        let mut len = 0;
+        let mut i = 0;
+        while i < N {
+            len += self.mask[i].count_ones() as usize;
+            i += 1;
+        }
+        len
+    }
+
+    /// Returns `true` if this set contains no bytes.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the smallest byte in this set, or `None` if the set is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// assert_eq!(AsciiSet::LOWERCASE.min(), Some(b'a'));
+    /// ```
+    pub const fn min(&self) -> Option<u8> {
+        let mut i = 0;
+        while i < N {
+            let chunk = self.mask[i];
+            if chunk != 0 {
+                return Some((i * BITS_PER_CHUNK + chunk.trailing_zeros() as usize) as u8);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Returns the largest byte in this set, or `None` if the set is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// assert_eq!(AsciiSet::LOWERCASE.max(), Some(b'z'));
+    /// ```
+    pub const fn max(&self) -> Option<u8> {
+        let mut i = N;
+        while i > 0 {
+            i -= 1;
+            let chunk = self.mask[i];
+            if chunk != 0 {
+                let top = BITS_PER_CHUNK - 1 - chunk.leading_zeros() as usize;
+                return Some((i * BITS_PER_CHUNK + top) as u8);
+            }
+        }
+        None
+    }
+
+    /// Returns an iterator over the bytes contained in this set, in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// let set = AsciiSet::new().add_bytes(b"ba");
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![b'a', b'b']);
+    /// ```
+    pub const fn iter(&self) -> Iter<N> {
+        Iter {
+            mask: self.mask,
+            chunk: 0,
+        }
+    }
+
+    /// Returns an iterator over the contiguous runs of bytes in this set, as
+    /// inclusive ranges in ascending order.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// let set = AsciiSet::DIGITS.add(b'a');
+    /// assert_eq!(set.ranges().collect::<Vec<_>>(), vec![b'0'..=b'9', b'a'..=b'a']);
+    /// ```
+    pub const fn ranges(&self) -> Ranges<N> {
+        Ranges {
+            iter: self.iter(),
+            next: None,
+        }
+    }
+
+    /// Builds a set containing exactly the bytes covered by `ranges`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::AsciiSet;
+    /// const DIGITS_AND_A: AsciiSet = AsciiSet::from_ranges(&[b'0'..=b'9', b'a'..=b'a']);
+    /// assert_eq!(DIGITS_AND_A, AsciiSet::DIGITS.add(b'a'));
+    /// ```
+    pub const fn from_ranges(ranges: &[RangeInclusive<u8>]) -> Self {
+        let mut set = Self::blank();
+        let mut i = 0;
+        while i < ranges.len() {
+            set = set.add_range(*ranges[i].start()..=*ranges[i].end());
+            i += 1;
+        }
+        set
+    }
+}
+
+/// An iterator over the contiguous runs of bytes in an [`AnyByteSet`], as
+/// inclusive ranges in ascending order. Returned by [`AnyByteSet::ranges`].
+#[derive(Clone, Debug)]
+pub struct Ranges<const N: usize> {
+    iter: Iter<N>,
+    next: Option<u8>,
+}
+
+impl<const N: usize> Iterator for Ranges<N> {
+    type Item = RangeInclusive<u8>;
+
+    fn next(&mut self) -> Option<RangeInclusive<u8>> {
+        let start = self.next.take().or_else(|| self.iter.next())?;
+        let mut end = start;
+        loop {
+            match self.iter.next() {
+                Some(byte) if Some(byte) == end.checked_add(1) => end = byte,
+                other => {
+                    self.next = other;
+                    break;
+                }
+            }
+        }
+        Some(start..=end)
+    }
+}
+
+impl<const N: usize> core::ops::BitOr for AnyByteSet<N> {
+    type Output = Self;
+
+    /// Returns the union of the two sets. See [`AnyByteSet::union`].
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl<const N: usize> core::ops::BitAnd for AnyByteSet<N> {
+    type Output = Self;
+
+    /// Returns the intersection of the two sets. See [`AnyByteSet::intersection`].
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl<const N: usize> core::ops::Sub for AnyByteSet<N> {
+    type Output = Self;
+
+    /// Returns the set of bytes in `self` but not `rhs`. See [`AnyByteSet::difference`].
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(rhs)
+    }
+}
+
+impl<const N: usize> core::ops::BitXor for AnyByteSet<N> {
+    type Output = Self;
+
+    /// Returns the set of bytes in exactly one of the two sets.
+    /// See [`AnyByteSet::symmetric_difference`].
+    fn bitxor(self, rhs: Self) -> Self {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl<const N: usize> core::ops::Not for AnyByteSet<N> {
+    type Output = Self;
+
+    /// Returns the set of all bytes not in `self`. See [`AnyByteSet::complement`].
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+/// An iterator over the bytes contained in an [`AnyByteSet`], in ascending order.
+/// Returned by [`AnyByteSet::iter`] and by the `IntoIterator` implementations.
+#[derive(Clone, Copy, Debug)]
+pub struct Iter<const N: usize> {
+    mask: [Chunk; N],
+    chunk: usize,
+}
+
+impl<const N: usize> Iterator for Iter<N> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        while self.chunk < N {
+            let chunk = self.mask[self.chunk];
+            if chunk == 0 {
+                self.chunk += 1;
+                continue;
+            }
+            let lowest = chunk & chunk.wrapping_neg();
+            self.mask[self.chunk] &= !lowest;
+            let bit = lowest.trailing_zeros() as usize;
+            return Some((self.chunk * BITS_PER_CHUNK + bit) as u8);
+        }
+        None
+    }
+}
+
+impl<const N: usize> IntoIterator for AnyByteSet<N> {
+    type Item = u8;
+    type IntoIter = Iter<N>;
+
+    fn into_iter(self) -> Iter<N> {
+        self.iter()
+    }
+}
+
+impl<const N: usize> IntoIterator for &AnyByteSet<N> {
+    type Item = u8;
+    type IntoIter = Iter<N>;
+
+    fn into_iter(self) -> Iter<N> {
+        self.iter()
+    }
+}
+
+/// A mapping of every byte to a small equivalence-class id, where two bytes
+/// share a class iff they have the same membership profile across whatever
+/// sets or stacks produced them (see `classes` on the set and stack types).
+/// This lets transition tables be indexed by a compact alphabet instead of by
+/// all 256 bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ByteClasses {
+    class_of: [u8; 2 * ASCII_RANGE_LEN],
+    num_classes: usize,
+}
+
+impl ByteClasses {
+    /// Returns the class id of the given byte.
+    #[inline]
+    pub const fn get(&self, byte: u8) -> u8 {
+        self.class_of[byte as usize]
+    }
+
+    /// Returns the number of distinct classes.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.num_classes
+    }
+
+    /// Returns `true` if there are no classes. This never happens in
+    /// practice, since every byte belongs to some class.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.num_classes == 0
+    }
+
+    /// Returns an iterator over one representative byte per class, in
+    /// ascending order of class id.
+    pub fn representatives(&self) -> impl Iterator<Item = u8> + '_ {
+        // `num_classes` can be as large as 256 (every byte its own class), so
+        // this range must stay `usize`; only the per-class id compared
+        // against `class_of` needs truncating down to `u8`.
+        (0..self.num_classes).map(move |class| {
+            let class = class as u8;
+            self.class_of
+                .iter()
+                .position(|&c| c == class)
+                .expect("every class has at least one representative byte") as u8
+        })
+    }
+}
+
+/// Builds a [`ByteClasses`] by evaluating `profile` for every byte and
+/// collapsing bytes that share a profile into the same class, in the order
+/// the profiles are first seen.
+fn byte_classes_from_profiles(profile: impl Fn(u8) -> u8) -> ByteClasses {
+    let mut class_of = [0u8; 2 * ASCII_RANGE_LEN];
+    let mut profile_to_class = [None; 256];
+    let mut num_classes = 0usize;
+    for byte in 0..=u8::MAX {
+        let p = profile(byte);
+        let class = match profile_to_class[p as usize] {
+            Some(class) => class,
+            None => {
+                let class = num_classes as u8;
+                profile_to_class[p as usize] = Some(class);
+                num_classes += 1;
+                class
+            }
+        };
+        class_of[byte as usize] = class;
+    }
+    ByteClasses {
+        class_of,
+        num_classes,
+    }
+}
+
+/// A pair of 16-entry lookup tables that reconstruct a 256-entry membership
+/// table from a byte's low and high nibbles, i.e. `lo[b & 0x0F] & hi[b >> 4]
+/// == masks[b]` for every byte `b`. Not every `masks` table factors this way;
+/// use [`NibbleLuts::build`] to find out.
+struct NibbleLuts {
+    lo: [u8; 16],
+    hi: [u8; 16],
+}
+
+impl NibbleLuts {
+    /// Attempts to factor `masks` into a pair of nibble lookup tables,
+    /// returning `None` if `masks` is not nibble-separable.
+    fn build(masks: &[u8; 256]) -> Option<Self> {
+        let mut hi = [0u8; 16];
+        for (h, slot) in hi.iter_mut().enumerate() {
+            for l in 0..16 {
+                *slot |= masks[h * 16 + l];
+            }
+        }
+        let mut lo = [0xFFu8; 16];
+        for (l, slot) in lo.iter_mut().enumerate() {
+            for (h, &required) in hi.iter().enumerate() {
+                *slot &= masks[h * 16 + l] | !required;
+            }
+        }
+        let luts = Self { lo, hi };
+        for (byte, &expected) in masks.iter().enumerate() {
+            let got = luts.lo[byte & 0x0F] & luts.hi[byte >> 4];
+            if got != expected {
+                return None;
+            }
+        }
+        Some(luts)
+    }
+
+    fn classify(&self, input: &[u8], out: &mut [u8]) {
+        // With the (opt-in) `std` feature, detect `ssse3` at runtime via
+        // `is_x86_feature_detected!`, which is the common case and needs no
+        // special build flags. Without `std`, that macro's CPUID cache isn't
+        // available, so fall back to a compile-time opt-in instead: build
+        // with `-C target-feature=+ssse3` (or `-C target-cpu=native`).
+        #[cfg(all(target_arch = "x86_64", feature = "std"))]
+        {
+            if std::is_x86_feature_detected!("ssse3") {
+                // SAFETY: the `ssse3` feature was just confirmed to be available.
+                unsafe { self.classify_ssse3(input, out) };
+            } else {
+                self.classify_scalar(input, out);
+            }
+        }
+        #[cfg(all(target_arch = "x86_64", not(feature = "std"), target_feature = "ssse3"))]
+        // SAFETY: `ssse3` is enabled for this whole compilation unit, checked above.
+        unsafe {
+            self.classify_ssse3(input, out);
+        }
+        #[cfg(not(any(
+            all(target_arch = "x86_64", feature = "std"),
+            all(target_arch = "x86_64", not(feature = "std"), target_feature = "ssse3")
+        )))]
+        self.classify_scalar(input, out);
+    }
+
+    fn classify_scalar(&self, input: &[u8], out: &mut [u8]) {
+        for (&byte, slot) in input.iter().zip(out) {
+            *slot = self.lo[(byte & 0x0F) as usize] & self.hi[(byte >> 4) as usize];
+        }
+    }
+
+    /// Classifies 16 bytes at a time using `pshufb` to perform both nibble
+    /// lookups, falling back to the scalar path for the remaining tail.
+    ///
+    /// # Safety
+    /// The caller must ensure the `ssse3` target feature is available.
+    #[cfg(all(
+        target_arch = "x86_64",
+        any(feature = "std", target_feature = "ssse3")
+    ))]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn classify_ssse3(&self, input: &[u8], out: &mut [u8]) {
+        use core::arch::x86_64::{
+            __m128i, _mm_and_si128, _mm_loadu_si128, _mm_set1_epi8, _mm_shuffle_epi8,
+            _mm_srli_epi16, _mm_storeu_si128,
+        };
+
+        let lo_lut = _mm_loadu_si128(self.lo.as_ptr() as *const __m128i);
+        let hi_lut = _mm_loadu_si128(self.hi.as_ptr() as *const __m128i);
+        let low_nibble_mask = _mm_set1_epi8(0x0F);
+
+        let chunks = input.len() / 16;
+        for i in 0..chunks {
+            let bytes = _mm_loadu_si128(input.as_ptr().add(i * 16) as *const __m128i);
+            let lo_idx = _mm_and_si128(bytes, low_nibble_mask);
+            let hi_idx = _mm_and_si128(_mm_srli_epi16(bytes, 4), low_nibble_mask);
+            let classified = _mm_and_si128(
+                _mm_shuffle_epi8(lo_lut, lo_idx),
+                _mm_shuffle_epi8(hi_lut, hi_idx),
+            );
+            _mm_storeu_si128(out.as_mut_ptr().add(i * 16) as *mut __m128i, classified);
+        }
+        self.classify_scalar(&input[chunks * 16..], &mut out[chunks * 16..]);
+    }
+}
+
+/// Classifies every byte of `input` according to `profile`, vectorizing the
+/// bulk of the work when `profile`'s 256-entry table factors into a pair of
+/// nibble lookup tables (see [`NibbleLuts`]), and falling back to a plain
+/// scalar loop otherwise.
+fn classify_bytes(profile: impl Fn(u8) -> u8, input: &[u8], out: &mut [u8]) {
+    assert!(
+        out.len() >= input.len(),
+        "`out` must be at least as long as `input`"
+    );
+    let mut masks = [0u8; 256];
+    for (byte, slot) in masks.iter_mut().enumerate() {
+        *slot = profile(byte as u8);
+    }
+    match NibbleLuts::build(&masks) {
+        Some(luts) => luts.classify(input, out),
+        None => {
+            for (&byte, slot) in input.iter().zip(out) {
+                *slot = masks[byte as usize];
+            }
+        }
+    }
 }
 
 impl<T> AsciiStack<T> {
@@ -287,6 +819,82 @@ impl<T> AsciiStack<T> {
     pub fn contains<B: Bit>(&self, byte: u8) -> bool {
         byte < ASCII_RANGE_LEN as u8 && self.masks[byte as usize] & (1 << B::NUMBER) != 0
     }
+
+    /// Groups all bytes into equivalence classes according to their
+    /// membership profile across every set stacked so far.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::{bits::*, AsciiSet, AsciiStack};
+    ///
+    /// const STACK: AsciiStack<B2> = AsciiStack::new()
+    ///     .add_set(AsciiSet::LOWERCASE)
+    ///     .add_set(AsciiSet::DIGITS);
+    /// let classes = STACK.classes();
+    /// assert_eq!(classes.get(b'a'), classes.get(b'z'));
+    /// assert_ne!(classes.get(b'a'), classes.get(b'0'));
+    /// ```
+    pub fn classes(&self) -> ByteClasses {
+        byte_classes_from_profiles(|byte| {
+            if byte < ASCII_RANGE_LEN as u8 {
+                self.masks[byte as usize]
+            } else {
+                0
+            }
+        })
+    }
+
+    /// Writes, for every byte of `input`, its membership bitmask across
+    /// every set stacked so far to the corresponding slot of `out`.
+    ///
+    /// On `x86_64`, this is vectorized with `pshufb` whenever the mask
+    /// table is nibble-separable (see [`NibbleLuts`]). With the `std`
+    /// feature enabled, that path is chosen by runtime CPU detection; on a
+    /// plain `no_std` build it instead requires the `ssse3` target feature
+    /// to be enabled at compile time (e.g. `-C target-feature=+ssse3` or
+    /// `-C target-cpu=native`), and a default build without either of these
+    /// falls back to a scalar loop.
+    ///
+    /// # Panics
+    /// Panics if `out` is shorter than `input`.
+    pub fn classify(&self, input: &[u8], out: &mut [u8]) {
+        classify_bytes(
+            |byte| {
+                if byte < ASCII_RANGE_LEN as u8 {
+                    self.masks[byte as usize]
+                } else {
+                    0
+                }
+            },
+            input,
+            out,
+        )
+    }
+
+    /// Returns the length of the leading prefix of `input` whose bytes all
+    /// belong to the set at the position `B` in the stack.
+    pub fn match_len<B: Bit>(&self, input: &[u8]) -> usize {
+        input.iter().take_while(|&&byte| self.contains::<B>(byte)).count()
+    }
+
+    /// Splits `input` right after the leading prefix of bytes that all
+    /// belong to the set at the position `B` in the stack, returning
+    /// `(prefix, rest)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::{bits::*, AsciiSet, AsciiStack};
+    ///
+    /// const STACK: AsciiStack<B1> = AsciiStack::new().add_set(AsciiSet::ALPHANUMERIC);
+    /// type Alphanumeric = B0;
+    ///
+    /// let (ident, rest) = STACK.split_first_span::<Alphanumeric>(b"ab12 cd");
+    /// assert_eq!(ident, b"ab12");
+    /// assert_eq!(rest, b" cd");
+    /// ```
+    pub fn split_first_span<'a, B: Bit>(&self, input: &'a [u8]) -> (&'a [u8], &'a [u8]) {
+        input.split_at(self.match_len::<B>(input))
+    }
 }
 
 impl AsciiStack<B0> {
@@ -305,6 +913,66 @@ impl<T> ByteStack<T> {
     pub fn contains<B: Bit>(&self, byte: u8) -> bool {
         self.masks[byte as usize] & (1 << B::NUMBER) != 0
     }
+
+    /// Groups all bytes into equivalence classes according to their
+    /// membership profile across every set stacked so far.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::{bits::*, ByteSet, ByteStack};
+    ///
+    /// const STACK: ByteStack<B2> = ByteStack::new()
+    ///     .add_set(ByteSet::LOWERCASE)
+    ///     .add_set(ByteSet::DIGITS);
+    /// let classes = STACK.classes();
+    /// assert_eq!(classes.get(b'a'), classes.get(b'z'));
+    /// assert_ne!(classes.get(b'a'), classes.get(b'0'));
+    /// ```
+    pub fn classes(&self) -> ByteClasses {
+        byte_classes_from_profiles(|byte| self.masks[byte as usize])
+    }
+
+    /// Writes, for every byte of `input`, its membership bitmask across
+    /// every set stacked so far to the corresponding slot of `out`.
+    ///
+    /// On `x86_64`, this is vectorized with `pshufb` whenever the mask
+    /// table is nibble-separable (see [`NibbleLuts`]). With the `std`
+    /// feature enabled, that path is chosen by runtime CPU detection; on a
+    /// plain `no_std` build it instead requires the `ssse3` target feature
+    /// to be enabled at compile time (e.g. `-C target-feature=+ssse3` or
+    /// `-C target-cpu=native`), and a default build without either of these
+    /// falls back to a scalar loop.
+    ///
+    /// # Panics
+    /// Panics if `out` is shorter than `input`.
+    pub fn classify(&self, input: &[u8], out: &mut [u8]) {
+        classify_bytes(|byte| self.masks[byte as usize], input, out)
+    }
+
+    /// Returns the length of the leading prefix of `input` whose bytes all
+    /// belong to the set at the position `B` in the stack.
+    pub fn match_len<B: Bit>(&self, input: &[u8]) -> usize {
+        input.iter().take_while(|&&byte| self.contains::<B>(byte)).count()
+    }
+
+    /// Splits `input` right after the leading prefix of bytes that all
+    /// belong to the set at the position `B` in the stack, returning
+    /// `(prefix, rest)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bset::{bits::*, ByteSet, ByteStack};
+    ///
+    /// const STACK: ByteStack<B1> = ByteStack::new().add_set(ByteSet::ALPHANUMERIC);
+    /// type Alphanumeric = B0;
+    ///
+    /// let (ident, rest) = STACK.split_first_span::<Alphanumeric>(b"ab12 cd");
+    /// assert_eq!(ident, b"ab12");
+    /// assert_eq!(rest, b" cd");
+    /// ```
+    pub fn split_first_span<'a, B: Bit>(&self, input: &'a [u8]) -> (&'a [u8], &'a [u8]) {
+        input.split_at(self.match_len::<B>(input))
+    }
 }
 
 impl ByteStack<B0> {
@@ -317,6 +985,48 @@ impl ByteStack<B0> {
     }
 }
 
+impl<T, const N: usize> AnyByteStack<T, N> {
+    /// Removes the set at the position `B` in this stack, so no byte is
+    /// reported as a member of it by `contains` any more. Unlike `add_set`,
+    /// this does not consume a position in the stack.
+    pub const fn remove_set<B: Bit>(&self) -> Self {
+        let mut masks = self.masks;
+        let mut i = 0;
+        while i < N {
+            masks[i] &= !(1 << B::NUMBER);
+            i += 1;
+        }
+        Self {
+            masks,
+            current: PhantomData,
+        }
+    }
+
+    /// Replaces the set at the position `B` in this stack with `aset`,
+    /// equivalent to `remove_set::<B>` followed by re-adding `aset` at that
+    /// same position.
+    pub const fn replace_set<B: Bit, const M: usize>(&self, aset: AnyByteSet<M>) -> Self {
+        let removed = self.remove_set::<B>();
+        let mut masks = removed.masks;
+        let mask = aset.mask;
+        let mut i = 0;
+        while i < M {
+            let mut j = 0;
+            while j < BITS_PER_CHUNK {
+                if mask[i] & (1 << j) != 0 {
+                    masks[i * BITS_PER_CHUNK + j] |= 1 << B::NUMBER;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        Self {
+            masks,
+            current: PhantomData,
+        }
+    }
+}
+
 // TODO: Implement this generically once generic bounds are stable for const fns.
 macro_rules! implement_add_set {
     ($($ty:ty),*) => {